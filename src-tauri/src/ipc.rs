@@ -0,0 +1,365 @@
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::pomodoro::POMODORO_TIMER_ID;
+use crate::timer::{Timer, TimerMode, TimerState};
+
+/// Requests accepted over the headless IPC socket, one per framed message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Command {
+    Create { id: String, name: String, duration_ms: i64 },
+    List,
+    Start { id: String },
+    Pause { id: String },
+    Resume { id: String },
+    Reset { id: String },
+    Delete { id: String },
+    Toggle { id: String },
+}
+
+/// Responses returned over the headless IPC socket
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum Answer {
+    Ok,
+    Timers(Vec<Timer>),
+    Error(String),
+}
+
+/// Env var that overrides the control-channel location, e.g. for running
+/// multiple instances side by side or pointing a test client at a scratch path.
+pub const SOCKET_PATH_ENV: &str = "FOCUSNOOK_SOCKET_PATH";
+
+/// Default control-channel location: a Unix domain socket on Unix, a named
+/// pipe path on Windows. Override via `FOCUSNOOK_SOCKET_PATH` or `spawn`'s
+/// `socket_path` argument.
+#[cfg(unix)]
+pub fn default_socket_path() -> PathBuf {
+    std::env::temp_dir().join("focusnook.sock")
+}
+
+#[cfg(windows)]
+pub fn default_socket_path() -> PathBuf {
+    PathBuf::from(r"\\.\pipe\focusnook")
+}
+
+/// Spawns the IPC listener task alongside the Tauri app so focus timers can
+/// be controlled without the GUI focused. `socket_path` takes precedence
+/// over `FOCUSNOOK_SOCKET_PATH`, which takes precedence over the default.
+pub fn spawn(app: AppHandle, timers: TimerState, socket_path: Option<PathBuf>) {
+    let path = socket_path
+        .or_else(|| std::env::var_os(SOCKET_PATH_ENV).map(PathBuf::from))
+        .unwrap_or_else(default_socket_path);
+    tokio::spawn(async move {
+        if let Err(e) = listen(app, timers, path).await {
+            println!("IPC listener stopped: {}", e);
+        }
+    });
+}
+
+#[cfg(unix)]
+async fn listen(app: AppHandle, timers: TimerState, path: PathBuf) -> std::io::Result<()> {
+    // A stale socket file from a previous run would otherwise make bind fail.
+    let _ = std::fs::remove_file(&path);
+    let listener = tokio::net::UnixListener::bind(&path)?;
+    println!("IPC listening on {}", path.display());
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let app = app.clone();
+        let timers = timers.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, app, timers).await {
+                println!("IPC connection error: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(windows)]
+async fn listen(app: AppHandle, timers: TimerState, path: PathBuf) -> std::io::Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let pipe_name = path.to_string_lossy().to_string();
+    println!("IPC listening on {}", pipe_name);
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(&pipe_name)?;
+
+    loop {
+        server.connect().await?;
+        let connected = server;
+        server = ServerOptions::new().create(&pipe_name)?;
+
+        let app = app.clone();
+        let timers = timers.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(connected, app, timers).await {
+                println!("IPC connection error: {}", e);
+            }
+        });
+    }
+}
+
+/// Largest frame we'll allocate a buffer for. Every real `Command` is tiny;
+/// this just bounds what a misbehaving or malicious local client can make
+/// us allocate from a single 4-byte length prefix.
+const MAX_FRAME_LEN: usize = 64 * 1024;
+
+/// Reads/writes 4-byte big-endian length-prefixed CBOR messages off one
+/// connection until the peer disconnects, so a client can issue several
+/// commands without reconnecting. A frame longer than `MAX_FRAME_LEN` is
+/// answered with an error and the connection is then closed.
+async fn handle_connection<S>(mut stream: S, app: AppHandle, timers: TimerState) -> std::io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        let mut len_buf = [0u8; 4];
+        if stream.read_exact(&mut len_buf).await.is_err() {
+            return Ok(()); // peer closed the connection
+        }
+        let len = u32::from_be_bytes(len_buf) as usize;
+
+        if len > MAX_FRAME_LEN {
+            let answer = Answer::Error(format!("Frame too large: {} bytes (max {})", len, MAX_FRAME_LEN));
+            let encoded = serde_cbor::to_vec(&answer)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            stream.write_all(&(encoded.len() as u32).to_be_bytes()).await?;
+            stream.write_all(&encoded).await?;
+            return Ok(());
+        }
+
+        let mut payload = vec![0u8; len];
+        stream.read_exact(&mut payload).await?;
+
+        let answer = match serde_cbor::from_slice::<Command>(&payload) {
+            Ok(command) => dispatch(command, &app, &timers),
+            Err(e) => Answer::Error(format!("Malformed command: {}", e)),
+        };
+
+        let encoded = serde_cbor::to_vec(&answer)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        stream.write_all(&(encoded.len() as u32).to_be_bytes()).await?;
+        stream.write_all(&encoded).await?;
+    }
+}
+
+fn dispatch(command: Command, app: &AppHandle, timers: &TimerState) -> Answer {
+    match command {
+        Command::Create { id, name, duration_ms } => create(timers, id, name, duration_ms),
+        Command::List => Answer::Timers(timers.get_all_timers()),
+        Command::Start { id } => guarded(&id, || result_to_answer(timers.start_timer(app.clone(), &id))),
+        Command::Pause { id } => guarded(&id, || result_to_answer(timers.pause_timer(app.clone(), &id))),
+        Command::Resume { id } => guarded(&id, || result_to_answer(timers.resume_timer(app.clone(), &id))),
+        Command::Reset { id } => guarded(&id, || result_to_answer(timers.reset_timer(app.clone(), &id))),
+        Command::Delete { id } => guarded(&id, || result_to_answer(timers.delete_timer(&id))),
+        Command::Toggle { id } => guarded(&id, || toggle(timers, app, &id)),
+    }
+}
+
+/// The Pomodoro controller owns `POMODORO_TIMER_ID` exclusively; letting an
+/// external command touch it directly would wreck the controller's
+/// bookkeeping without it ever finding out (it would keep thinking a phase
+/// is in flight with no timer backing it). Runs `op` only if `id` isn't reserved.
+fn guarded(id: &str, op: impl FnOnce() -> Answer) -> Answer {
+    if id == POMODORO_TIMER_ID {
+        return Answer::Error("This timer id is reserved".to_string());
+    }
+    op()
+}
+
+/// Rejects the reserved Pomodoro timer id, then creates a single-shot timer.
+/// Doesn't need an `AppHandle` since a freshly created timer isn't running.
+fn create(timers: &TimerState, id: String, name: String, duration_ms: i64) -> Answer {
+    guarded(&id, || match timers.create_timer(id.clone(), name, duration_ms, TimerMode::SingleShot, None) {
+        Ok(_) => Answer::Ok,
+        Err(e) => Answer::Error(e),
+    })
+}
+
+/// Starts a stopped/paused timer, or pauses a running one.
+fn toggle(timers: &TimerState, app: &AppHandle, id: &str) -> Answer {
+    match toggle_target(timers, id) {
+        Ok(running) => {
+            let result = if running {
+                timers.pause_timer(app.clone(), id)
+            } else {
+                timers.start_timer(app.clone(), id)
+            };
+            result_to_answer(result)
+        }
+        Err(answer) => answer,
+    }
+}
+
+/// Looks up whether `id` is currently running, so `toggle` knows which way
+/// to flip it. Split out from `toggle` so the not-found case is testable
+/// without an `AppHandle`.
+fn toggle_target(timers: &TimerState, id: &str) -> Result<bool, Answer> {
+    timers
+        .get_all_timers()
+        .into_iter()
+        .find(|t| t.id == id)
+        .map(|t| t.running)
+        .ok_or_else(|| Answer::Error("Timer not found".to_string()))
+}
+
+fn result_to_answer(result: Result<(), String>) -> Answer {
+    match result {
+        Ok(()) => Answer::Ok,
+        Err(e) => Answer::Error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_cbor_round_trip() {
+        let commands = vec![
+            Command::Create { id: "a".to_string(), name: "Focus".to_string(), duration_ms: 5000 },
+            Command::List,
+            Command::Start { id: "a".to_string() },
+            Command::Pause { id: "a".to_string() },
+            Command::Resume { id: "a".to_string() },
+            Command::Reset { id: "a".to_string() },
+            Command::Delete { id: "a".to_string() },
+            Command::Toggle { id: "a".to_string() },
+        ];
+
+        for command in commands {
+            let encoded = serde_cbor::to_vec(&command).unwrap();
+            let decoded: Command = serde_cbor::from_slice(&encoded).unwrap();
+            assert_eq!(decoded, command);
+        }
+    }
+
+    #[test]
+    fn test_answer_cbor_round_trip() {
+        for answer in [Answer::Ok, Answer::Error("boom".to_string())] {
+            let encoded = serde_cbor::to_vec(&answer).unwrap();
+            let decoded: Answer = serde_cbor::from_slice(&encoded).unwrap();
+            match (answer, decoded) {
+                (Answer::Ok, Answer::Ok) => {}
+                (Answer::Error(a), Answer::Error(b)) => assert_eq!(a, b),
+                other => panic!("round trip changed variant: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_rejects_reserved_pomodoro_id() {
+        let timers = TimerState::new();
+        let answer = create(&timers, POMODORO_TIMER_ID.to_string(), "Focus".to_string(), 5000);
+
+        match answer {
+            Answer::Error(e) => assert_eq!(e, "This timer id is reserved"),
+            other => panic!("expected Answer::Error, got {:?}", other),
+        }
+        assert_eq!(timers.get_all_timers().len(), 0);
+    }
+
+    #[test]
+    fn test_create_rejects_duplicate_id() {
+        let timers = TimerState::new();
+        assert!(matches!(create(&timers, "a".to_string(), "First".to_string(), 1000), Answer::Ok));
+
+        let answer = create(&timers, "a".to_string(), "Second".to_string(), 2000);
+        match answer {
+            Answer::Error(e) => assert_eq!(e, "Timer with this ID already exists"),
+            other => panic!("expected Answer::Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_list_returns_created_timers() {
+        let timers = TimerState::new();
+        create(&timers, "a".to_string(), "Focus".to_string(), 5000);
+
+        match Answer::Timers(timers.get_all_timers()) {
+            Answer::Timers(all) => {
+                assert_eq!(all.len(), 1);
+                assert_eq!(all[0].id, "a");
+            }
+            other => panic!("expected Answer::Timers, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_delete_not_found() {
+        let timers = TimerState::new();
+        let answer = result_to_answer(timers.delete_timer("missing"));
+
+        match answer {
+            Answer::Error(e) => assert_eq!(e, "Timer not found"),
+            other => panic!("expected Answer::Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_delete_rejects_reserved_pomodoro_id() {
+        let timers = TimerState::new();
+        let answer = guarded(POMODORO_TIMER_ID, || result_to_answer(timers.delete_timer(POMODORO_TIMER_ID)));
+
+        match answer {
+            Answer::Error(e) => assert_eq!(e, "This timer id is reserved"),
+            other => panic!("expected Answer::Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_start_rejects_reserved_pomodoro_id() {
+        // Start needs an AppHandle to actually run, so the only way to verify
+        // this without one is to confirm it never gets that far: `guarded`
+        // must short-circuit on the reserved id before `op` is ever invoked.
+        let answer = guarded(POMODORO_TIMER_ID, || unreachable!("start_timer should not run for the reserved id"));
+
+        match answer {
+            Answer::Error(e) => assert_eq!(e, "This timer id is reserved"),
+            other => panic!("expected Answer::Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_reset_rejects_reserved_pomodoro_id() {
+        let answer = guarded(POMODORO_TIMER_ID, || unreachable!("reset_timer should not run for the reserved id"));
+
+        match answer {
+            Answer::Error(e) => assert_eq!(e, "This timer id is reserved"),
+            other => panic!("expected Answer::Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_guarded_allows_non_reserved_id() {
+        let answer = guarded("some-other-id", || Answer::Ok);
+        assert!(matches!(answer, Answer::Ok));
+    }
+
+    #[test]
+    fn test_toggle_target_not_found() {
+        let timers = TimerState::new();
+
+        match toggle_target(&timers, "missing") {
+            Ok(running) => panic!("expected Err, got Ok({})", running),
+            Err(Answer::Error(e)) => assert_eq!(e, "Timer not found"),
+            Err(other) => panic!("expected Answer::Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_toggle_target_reflects_running_state() {
+        let timers = TimerState::new();
+        create(&timers, "a".to_string(), "Focus".to_string(), 5000);
+
+        // A freshly created timer isn't running yet, so toggle should start it.
+        match toggle_target(&timers, "a") {
+            Ok(running) => assert!(!running),
+            Err(e) => panic!("expected Ok(false), got {:?}", e),
+        }
+    }
+}
@@ -1,18 +1,41 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod ipc;
+mod pomodoro;
 mod timer;
 
-use tauri::{App, AppHandle, Manager};
-use timer::{TimerState, Timer};
+use tauri::{App, AppHandle, Listener, Manager};
+use pomodoro::{PomodoroConfig, PomodoroSnapshot, PomodoroState};
+use timer::{Timer, TimerMode, TimerState};
+
+/// Minimal shape of the `timer:done` event payload, just enough to tell
+/// whether it belongs to the Pomodoro controller's phase timer.
+#[derive(serde::Deserialize)]
+struct TimerDoneEvent {
+  timer_id: String,
+}
+
+/// The Pomodoro controller owns `POMODORO_TIMER_ID` exclusively; an external
+/// caller creating, deleting, or otherwise mutating it directly would wreck
+/// the controller's bookkeeping without it ever finding out.
+fn reject_reserved_id(timer_id: &str) -> Result<(), String> {
+  if timer_id == pomodoro::POMODORO_TIMER_ID {
+    return Err("This timer id is reserved".to_string());
+  }
+  Ok(())
+}
 
 #[tauri::command]
 async fn create_timer(
   state: tauri::State<'_, TimerState>,
   id: String,
   name: String,
-  duration_ms: i64
+  duration_ms: i64,
+  mode: TimerMode,
+  max_cycles: Option<u32>
 ) -> Result<Timer, String> {
   println!("create_timer called with id: {}, name: {}, duration_ms: {}", id, name, duration_ms);
-  let result = state.create_timer(id, name, duration_ms);
+  reject_reserved_id(&id)?;
+  let result = state.create_timer(id, name, duration_ms, mode, max_cycles);
   match &result {
     Ok(timer) => println!("Timer created successfully: {:?}", timer),
     Err(e) => println!("Failed to create timer: {}", e),
@@ -30,6 +53,7 @@ async fn delete_timer(
   state: tauri::State<'_, TimerState>,
   timer_id: String
 ) -> Result<(), String> {
+  reject_reserved_id(&timer_id)?;
   state.delete_timer(&timer_id)
 }
 
@@ -40,6 +64,7 @@ async fn start_timer(
   timer_id: String
 ) -> Result<(), String> {
   println!("start_timer called with timer_id: {}", timer_id);
+  reject_reserved_id(&timer_id)?;
   let result = state.start_timer(app, &timer_id);
   match &result {
     Ok(_) => println!("Timer started successfully"),
@@ -54,6 +79,7 @@ async fn pause_timer(
   state: tauri::State<'_, TimerState>,
   timer_id: String
 ) -> Result<(), String> {
+  reject_reserved_id(&timer_id)?;
   state.pause_timer(app, &timer_id)
 }
 
@@ -63,6 +89,7 @@ async fn resume_timer(
   state: tauri::State<'_, TimerState>,
   timer_id: String
 ) -> Result<(), String> {
+  reject_reserved_id(&timer_id)?;
   state.resume_timer(app, &timer_id)
 }
 
@@ -72,14 +99,75 @@ async fn reset_timer(
   state: tauri::State<'_, TimerState>,
   timer_id: String
 ) -> Result<(), String> {
+  reject_reserved_id(&timer_id)?;
   state.reset_timer(app, &timer_id)
 }
 
+#[tauri::command]
+async fn start_pomodoro(
+  app: AppHandle,
+  pomodoro: tauri::State<'_, PomodoroState>,
+  timers: tauri::State<'_, TimerState>,
+  config: Option<PomodoroConfig>
+) -> Result<PomodoroSnapshot, String> {
+  pomodoro.start_pomodoro(app, &timers, config)
+}
+
+#[tauri::command]
+async fn pause_pomodoro(
+  app: AppHandle,
+  pomodoro: tauri::State<'_, PomodoroState>,
+  timers: tauri::State<'_, TimerState>
+) -> Result<(), String> {
+  pomodoro.pause_pomodoro(app, &timers)
+}
+
+#[tauri::command]
+async fn skip_phase(
+  app: AppHandle,
+  pomodoro: tauri::State<'_, PomodoroState>,
+  timers: tauri::State<'_, TimerState>
+) -> Result<PomodoroSnapshot, String> {
+  pomodoro.skip_phase(app, &timers)
+}
+
+#[tauri::command]
+async fn get_pomodoro_state(
+  pomodoro: tauri::State<'_, PomodoroState>,
+  timers: tauri::State<'_, TimerState>
+) -> Result<PomodoroSnapshot, String> {
+  Ok(pomodoro.get_pomodoro_state(&timers))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
     .setup(|app: &mut App| {
-      app.manage(TimerState::new());
+      let timer_state = TimerState::load(&app.handle().clone());
+      app.manage(timer_state.clone());
+
+      let pomodoro_state = PomodoroState::load(&app.handle().clone(), &timer_state);
+      app.manage(pomodoro_state);
+
+      // Chain Pomodoro phases off the same `timer:done` event the frontend
+      // listens to, so the controller stays decoupled from the timer runner.
+      let app_handle = app.handle().clone();
+      app.listen("timer:done", move |event| {
+        let Ok(payload) = serde_json::from_str::<TimerDoneEvent>(event.payload()) else {
+          return;
+        };
+        if payload.timer_id != pomodoro::POMODORO_TIMER_ID {
+          return;
+        }
+        let pomodoro = app_handle.state::<PomodoroState>().inner().clone();
+        let timers = app_handle.state::<TimerState>().inner().clone();
+        pomodoro.advance_on_completion(app_handle.clone(), &timers);
+      });
+
+      // Let external tools (CLI, scripts, menubar helpers) control timers
+      // without the GUI focused.
+      ipc::spawn(app.handle().clone(), timer_state, None);
+
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
@@ -89,7 +177,11 @@ pub fn run() {
       start_timer,
       pause_timer,
       resume_timer,
-      reset_timer
+      reset_timer,
+      start_pomodoro,
+      pause_pomodoro,
+      skip_phase,
+      get_pomodoro_state
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
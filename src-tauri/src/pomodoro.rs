@@ -0,0 +1,353 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::timer::{Timer, TimerMode, TimerState};
+
+/// The id of the single `Timer` the Pomodoro controller drives. Only one
+/// phase is ever in flight, so a fixed id is enough to find it again.
+pub const POMODORO_TIMER_ID: &str = "pomodoro-session";
+
+const STORAGE_FILE_NAME: &str = "pomodoro.json";
+
+/// Which part of the work/break cycle is currently active
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroPhase {
+    fn label(&self) -> &'static str {
+        match self {
+            PomodoroPhase::Work => "Focus",
+            PomodoroPhase::ShortBreak => "Short Break",
+            PomodoroPhase::LongBreak => "Long Break",
+        }
+    }
+}
+
+/// Durations and cadence for a Pomodoro cycle
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct PomodoroConfig {
+    pub work_duration_ms: i64,
+    pub short_break_ms: i64,
+    pub long_break_ms: i64,
+    pub sessions_before_long_break: u32,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work_duration_ms: 25 * 60 * 1000,
+            short_break_ms: 5 * 60 * 1000,
+            long_break_ms: 15 * 60 * 1000,
+            sessions_before_long_break: 4,
+        }
+    }
+}
+
+fn duration_for(phase: PomodoroPhase, config: &PomodoroConfig) -> i64 {
+    match phase {
+        PomodoroPhase::Work => config.work_duration_ms,
+        PomodoroPhase::ShortBreak => config.short_break_ms,
+        PomodoroPhase::LongBreak => config.long_break_ms,
+    }
+}
+
+/// Given the phase that just finished, determine the next phase and the
+/// updated count of completed work sessions.
+fn next_phase(
+    current: PomodoroPhase,
+    completed_work_sessions: u32,
+    config: &PomodoroConfig,
+) -> (PomodoroPhase, u32) {
+    match current {
+        PomodoroPhase::Work => {
+            let completed = completed_work_sessions + 1;
+            let phase = if completed % config.sessions_before_long_break == 0 {
+                PomodoroPhase::LongBreak
+            } else {
+                PomodoroPhase::ShortBreak
+            };
+            (phase, completed)
+        }
+        PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => {
+            (PomodoroPhase::Work, completed_work_sessions)
+        }
+    }
+}
+
+/// Payload sent to the frontend whenever the active phase changes
+#[derive(serde::Serialize, Clone)]
+struct PomodoroPhasePayload {
+    phase: PomodoroPhase,
+    session_index: u32,
+}
+
+/// Enough state to rebuild the Pomodoro UI after a reload, combining the
+/// controller's own bookkeeping with the underlying timer's countdown.
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct PomodoroSnapshot {
+    pub active: bool,
+    pub phase: PomodoroPhase,
+    pub session_index: u32,
+    pub running: bool,
+    pub remaining_ms: i64,
+    pub duration_ms: i64,
+}
+
+#[derive(Default)]
+struct PomodoroInner {
+    config: PomodoroConfig,
+    phase: PomodoroPhase,
+    completed_work_sessions: u32,
+    active: bool,
+}
+
+impl Default for PomodoroPhase {
+    fn default() -> Self {
+        PomodoroPhase::Work
+    }
+}
+
+/// On-disk representation of the controller's own bookkeeping. The phase
+/// timer's countdown/deadline is persisted separately by [`TimerState`];
+/// this only needs to capture which phase we're in.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct PersistedPomodoro {
+    phase: PomodoroPhase,
+    completed_work_sessions: u32,
+    active: bool,
+}
+
+fn storage_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join(STORAGE_FILE_NAME))
+}
+
+/// Global Pomodoro controller shared via Tauri State. Drives the existing
+/// single-timer machinery in [`TimerState`] through its work/break phases.
+#[derive(Clone, Default)]
+pub struct PomodoroState(Arc<Mutex<PomodoroInner>>);
+
+impl PomodoroState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rehydrates the controller's phase/session bookkeeping from the
+    /// app-data directory, then reconciles it against the (already
+    /// rehydrated) phase timer so a restart mid-cycle doesn't leave the
+    /// controller stuck thinking no Pomodoro is in progress. Call once from
+    /// `setup`, after `TimerState::load`.
+    pub fn load(app: &AppHandle, timer_state: &TimerState) -> Self {
+        let state = Self::new();
+
+        let persisted = storage_path(app)
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice::<PersistedPomodoro>(&bytes).ok());
+
+        if let Some(persisted) = persisted {
+            let mut inner = state.0.lock().unwrap();
+            inner.phase = persisted.phase;
+            inner.completed_work_sessions = persisted.completed_work_sessions;
+            inner.active = persisted.active;
+        }
+
+        state.reconcile_after_restore(app, timer_state);
+        state
+    }
+
+    pub fn start_pomodoro(
+        &self,
+        app: AppHandle,
+        timer_state: &TimerState,
+        config: Option<PomodoroConfig>,
+    ) -> Result<PomodoroSnapshot, String> {
+        {
+            let mut inner = self.0.lock().map_err(|_| "Failed to lock pomodoro state".to_string())?;
+            inner.config = config.unwrap_or_default();
+            inner.phase = PomodoroPhase::Work;
+            inner.completed_work_sessions = 0;
+            inner.active = true;
+        }
+
+        self.save_to_disk(&app);
+        self.arm_phase(&app, timer_state)
+    }
+
+    pub fn pause_pomodoro(&self, app: AppHandle, timer_state: &TimerState) -> Result<(), String> {
+        timer_state.pause_timer(app, POMODORO_TIMER_ID)
+    }
+
+    pub fn skip_phase(
+        &self,
+        app: AppHandle,
+        timer_state: &TimerState,
+    ) -> Result<PomodoroSnapshot, String> {
+        {
+            let mut inner = self.0.lock().map_err(|_| "Failed to lock pomodoro state".to_string())?;
+            if !inner.active {
+                return Err("Pomodoro is not running".to_string());
+            }
+            let (next, completed) =
+                next_phase(inner.phase, inner.completed_work_sessions, &inner.config);
+            inner.phase = next;
+            inner.completed_work_sessions = completed;
+        }
+
+        self.save_to_disk(&app);
+        self.arm_phase(&app, timer_state)
+    }
+
+    pub fn get_pomodoro_state(&self, timer_state: &TimerState) -> PomodoroSnapshot {
+        self.snapshot(timer_state)
+    }
+
+    /// Called when the underlying phase timer reports `timer:done`. Advances
+    /// to the next phase automatically; a no-op if no Pomodoro is active.
+    pub fn advance_on_completion(&self, app: AppHandle, timer_state: &TimerState) {
+        let active = self.0.lock().unwrap().active;
+        if !active {
+            return;
+        }
+        if let Err(e) = self.skip_phase(app, timer_state) {
+            println!("Failed to advance pomodoro phase: {}", e);
+        }
+    }
+
+    /// Reconciles restored state against the (already rehydrated) phase
+    /// timer: if it's still running, `phase`/`active` (loaded from our own
+    /// persisted file) already match and there's nothing to do; if it
+    /// caught up to completion while the app was closed, advance to the
+    /// next phase the same way `advance_on_completion` would have; if it's
+    /// paused, the controller stays active in the same phase; if it's gone
+    /// entirely, there's nothing left to resume.
+    fn reconcile_after_restore(&self, app: &AppHandle, timer_state: &TimerState) {
+        if !self.0.lock().unwrap().active {
+            return;
+        }
+
+        let timer = timer_state
+            .get_all_timers()
+            .into_iter()
+            .find(|t| t.id == POMODORO_TIMER_ID);
+
+        match timer {
+            Some(t) if t.running => {}
+            Some(t) if t.completed => {
+                if let Err(e) = self.skip_phase(app.clone(), timer_state) {
+                    println!("Failed to advance pomodoro phase after restore: {}", e);
+                }
+            }
+            Some(_) => {}
+            None => {
+                self.0.lock().unwrap().active = false;
+            }
+        }
+    }
+
+    fn save_to_disk(&self, app: &AppHandle) {
+        let Some(path) = storage_path(app) else { return };
+
+        let persisted = {
+            let inner = self.0.lock().unwrap();
+            PersistedPomodoro {
+                phase: inner.phase,
+                completed_work_sessions: inner.completed_work_sessions,
+                active: inner.active,
+            }
+        };
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_vec_pretty(&persisted) {
+            let _ = std::fs::write(&path, json);
+        }
+    }
+
+    fn arm_phase(&self, app: &AppHandle, timer_state: &TimerState) -> Result<PomodoroSnapshot, String> {
+        let (phase, session_index, duration_ms) = {
+            let inner = self.0.lock().map_err(|_| "Failed to lock pomodoro state".to_string())?;
+            (inner.phase, inner.completed_work_sessions, duration_for(inner.phase, &inner.config))
+        };
+
+        // Replace any timer left over from the previous phase
+        let _ = timer_state.delete_timer(POMODORO_TIMER_ID);
+        timer_state.create_timer(
+            POMODORO_TIMER_ID.to_string(),
+            phase.label().to_string(),
+            duration_ms,
+            TimerMode::SingleShot,
+            None,
+        )?;
+        timer_state.start_timer(app.clone(), POMODORO_TIMER_ID)?;
+
+        let _ = app.emit("pomodoro:phase", PomodoroPhasePayload { phase, session_index });
+
+        Ok(self.snapshot(timer_state))
+    }
+
+    fn snapshot(&self, timer_state: &TimerState) -> PomodoroSnapshot {
+        let inner = self.0.lock().unwrap();
+        let timer: Option<Timer> = timer_state
+            .get_all_timers()
+            .into_iter()
+            .find(|t| t.id == POMODORO_TIMER_ID);
+
+        PomodoroSnapshot {
+            active: inner.active,
+            phase: inner.phase,
+            session_index: inner.completed_work_sessions,
+            running: timer.as_ref().map(|t| t.running).unwrap_or(false),
+            remaining_ms: timer.as_ref().map(|t| t.remaining_ms).unwrap_or(0),
+            duration_ms: timer.as_ref().map(|t| t.duration_ms).unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_phase_short_break_between_long_breaks() {
+        let config = PomodoroConfig::default();
+        let (phase, completed) = next_phase(PomodoroPhase::Work, 0, &config);
+        assert_eq!(phase, PomodoroPhase::ShortBreak);
+        assert_eq!(completed, 1);
+    }
+
+    #[test]
+    fn test_next_phase_long_break_after_n_sessions() {
+        let config = PomodoroConfig::default();
+        let (phase, completed) = next_phase(PomodoroPhase::Work, 3, &config);
+        assert_eq!(phase, PomodoroPhase::LongBreak);
+        assert_eq!(completed, 4);
+    }
+
+    #[test]
+    fn test_next_phase_break_returns_to_work() {
+        let config = PomodoroConfig::default();
+        let (phase, completed) = next_phase(PomodoroPhase::ShortBreak, 1, &config);
+        assert_eq!(phase, PomodoroPhase::Work);
+        assert_eq!(completed, 1);
+
+        let (phase, completed) = next_phase(PomodoroPhase::LongBreak, 4, &config);
+        assert_eq!(phase, PomodoroPhase::Work);
+        assert_eq!(completed, 4);
+    }
+
+    #[test]
+    fn test_get_pomodoro_state_before_start_is_inactive() {
+        let pomodoro = PomodoroState::new();
+        let timers = TimerState::new();
+        let snapshot = pomodoro.get_pomodoro_state(&timers);
+
+        assert!(!snapshot.active);
+        assert_eq!(snapshot.phase, PomodoroPhase::Work);
+        assert_eq!(snapshot.session_index, 0);
+    }
+}
@@ -1,11 +1,36 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter};
-use tokio::sync::oneshot;
-use tokio::task::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
 use chrono::Utc;
 
+/// How many past-due timers the driver will process in one wakeup before
+/// yielding back to the executor, so a pile-up of deadlines can't starve it.
+const MAX_EXPIRATIONS_PER_BATCH: usize = 16;
+
+/// Coarse cadence for `timer:tick` updates, independent of any single
+/// timer's deadline.
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait for mutations to settle before writing the persisted
+/// snapshot, so a burst of operations (e.g. creating several timers) only
+/// triggers one disk write.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+const STORAGE_FILE_NAME: &str = "timers.json";
+
+/// Whether a timer stops after reaching zero or re-arms itself for another cycle
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TimerMode {
+    #[default]
+    SingleShot,
+    Repeated,
+}
+
 /// Timer data that can be serialized and sent to the frontend
 #[derive(serde::Serialize, Clone, Debug)]
 pub struct Timer {
@@ -16,15 +41,40 @@ pub struct Timer {
     pub running: bool,
     pub completed: bool,
     pub created_at: String,
+    pub mode: TimerMode,
+    pub max_cycles: Option<u32>,
+    pub cycle_count: u32,
 }
 
-/// Global multi-timer state shared via Tauri State
-#[derive(Clone, Default)]
-pub struct TimerState(Arc<Mutex<MultiTimerInner>>);
+/// Global multi-timer state shared via Tauri State. All timing is driven by
+/// a single background task (see [`TimerState::drive`]) rather than one
+/// task per timer.
+#[derive(Clone)]
+pub struct TimerState(Arc<MultiTimerShared>);
+
+struct MultiTimerShared {
+    inner: Mutex<MultiTimerInner>,
+    /// Wakes the driver whenever a deadline is added, changed, or removed
+    /// so it can recompute what to sleep until.
+    notify: Notify,
+    driver_started: AtomicBool,
+    /// Captured from `load` (or lazily from the first command that touches
+    /// the driver), since the background task outlives any single command
+    /// invocation.
+    app: Mutex<Option<AppHandle>>,
+    /// Bumped on every mutation; a pending debounced save only writes if no
+    /// newer mutation has arrived while it was waiting.
+    save_generation: AtomicU64,
+}
 
 #[derive(Default)]
 struct MultiTimerInner {
     timers: HashMap<String, TimerInstance>,
+    /// Min-heap of (deadline, timer_id). Entries can go stale (timer paused,
+    /// reset, restarted, or deleted since being scheduled); the driver
+    /// verifies each entry against the timer's current `target_at` before
+    /// acting on it rather than trying to remove heap entries eagerly.
+    heap: BinaryHeap<Reverse<(Instant, String)>>,
 }
 
 struct TimerInstance {
@@ -33,11 +83,12 @@ struct TimerInstance {
     duration_ms: i64,
     remaining_ms: i64,
     target_at: Option<Instant>,
-    runner: Option<JoinHandle<()>>,
-    cancel_tx: Option<oneshot::Sender<()>>,
     running: bool,
     completed: bool,
     created_at: String,
+    mode: TimerMode,
+    max_cycles: Option<u32>,
+    cycle_count: u32,
 }
 
 /// Payload sent to frontend on every tick for a specific timer
@@ -56,14 +107,190 @@ struct TimerDonePayload {
     finished_at: String,
 }
 
+/// On-disk representation of a timer. A running timer's `deadline` is an
+/// absolute RFC3339 instant rather than the monotonic `Instant` used at
+/// runtime, since the latter resets across restarts.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct PersistedTimer {
+    id: String,
+    name: String,
+    duration_ms: i64,
+    remaining_ms: i64,
+    running: bool,
+    completed: bool,
+    created_at: String,
+    mode: TimerMode,
+    max_cycles: Option<u32>,
+    cycle_count: u32,
+    deadline: Option<String>,
+}
+
+impl Default for TimerState {
+    fn default() -> Self {
+        Self(Arc::new(MultiTimerShared {
+            inner: Mutex::new(MultiTimerInner::default()),
+            notify: Notify::new(),
+            driver_started: AtomicBool::new(false),
+            app: Mutex::new(None),
+            save_generation: AtomicU64::new(0),
+        }))
+    }
+}
+
+/// Converts a monotonic deadline into an absolute wall-clock instant by
+/// anchoring it to the current `Instant`/`Utc::now()` pair.
+fn instant_to_rfc3339(target: Instant) -> String {
+    let now_instant = Instant::now();
+    let now_utc = Utc::now();
+    let deadline_utc = if target >= now_instant {
+        now_utc + chrono::Duration::from_std(target - now_instant).unwrap_or_else(|_| chrono::Duration::zero())
+    } else {
+        now_utc - chrono::Duration::from_std(now_instant - target).unwrap_or_else(|_| chrono::Duration::zero())
+    };
+    deadline_utc.to_rfc3339()
+}
+
+fn storage_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join(STORAGE_FILE_NAME))
+}
+
+/// Result of reconciling one persisted timer against the current time,
+/// pulled out of `load` as a pure function so the catch-up arithmetic
+/// (multi-period counting, `max_cycles` exhaustion, the still-pending
+/// branch) can be unit tested without touching `TimerState`.
+struct RestoredTimer {
+    running: bool,
+    completed: bool,
+    remaining_ms: i64,
+    cycle_count: u32,
+    target_at: Option<Instant>,
+    /// Whether this timer's deadline passed while the app was closed, so a
+    /// catch-up `timer:done` should be emitted for it.
+    caught_up: bool,
+}
+
+/// A single-shot timer whose deadline already passed while the app was
+/// closed is marked completed; a `Repeated` timer in the same situation
+/// catches up every cycle that elapsed and keeps running (unless that
+/// exhausts its `max_cycles`), matching how it would have behaved had the
+/// app stayed open. `now_instant` anchors any new `target_at` relative to
+/// the monotonic clock `now_utc` was sampled alongside.
+fn restore_timer(persisted: &PersistedTimer, now_utc: chrono::DateTime<Utc>, now_instant: Instant) -> RestoredTimer {
+    let mut running = persisted.running;
+    let mut completed = persisted.completed;
+    let mut remaining_ms = persisted.remaining_ms;
+    let mut cycle_count = persisted.cycle_count;
+    let mut target_at = None;
+    let mut caught_up = false;
+
+    if running {
+        match persisted.deadline.as_deref().and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok()) {
+            Some(deadline) => {
+                let deadline_utc = deadline.with_timezone(&Utc);
+                if deadline_utc <= now_utc {
+                    caught_up = true;
+
+                    // A timer can be closed for several multiples of its own
+                    // interval, not just one, so count every period that
+                    // elapsed rather than assuming a single catch-up cycle.
+                    let duration_ms = persisted.duration_ms.max(1);
+                    let elapsed_ms = (now_utc - deadline_utc).num_milliseconds().max(0);
+                    let elapsed_periods = u32::try_from(1 + elapsed_ms / duration_ms).unwrap_or(u32::MAX);
+                    cycle_count = cycle_count.saturating_add(elapsed_periods);
+                    let exhausted = matches!(persisted.max_cycles, Some(max) if cycle_count >= max);
+
+                    if persisted.mode == TimerMode::Repeated && !exhausted {
+                        let into_current_period_ms = elapsed_ms % duration_ms;
+                        let remaining = Duration::from_millis((duration_ms - into_current_period_ms) as u64);
+                        remaining_ms = remaining.as_millis() as i64;
+                        target_at = Some(now_instant + remaining);
+                    } else {
+                        running = false;
+                        completed = true;
+                        remaining_ms = 0;
+                    }
+                } else {
+                    let remaining = (deadline_utc - now_utc).to_std().unwrap_or_default();
+                    remaining_ms = remaining.as_millis() as i64;
+                    target_at = Some(now_instant + remaining);
+                }
+            }
+            None => running = false,
+        }
+    }
+
+    RestoredTimer { running, completed, remaining_ms, cycle_count, target_at, caught_up }
+}
+
 impl TimerState {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn create_timer(&self, id: String, name: String, duration_ms: i64) -> Result<Timer, String> {
-        let mut inner = self.0.lock().map_err(|_| "Failed to lock timer state".to_string())?;
-        
+    /// Rehydrates persisted timers from the app-data directory. Call once
+    /// from `setup`. See [`restore_timer`] for how each timer's running
+    /// state is reconciled against the current time.
+    pub fn load(app: &AppHandle) -> Self {
+        let state = Self::new();
+        *state.0.app.lock().unwrap() = Some(app.clone());
+
+        let loaded = storage_path(app)
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice::<Vec<PersistedTimer>>(&bytes).ok())
+            .unwrap_or_default();
+
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        let mut caught_up_ids = Vec::new();
+
+        {
+            let mut inner = state.0.inner.lock().unwrap();
+            for persisted in loaded {
+                let restored = restore_timer(&persisted, now_utc, now_instant);
+                if restored.caught_up {
+                    caught_up_ids.push(persisted.id.clone());
+                }
+
+                let id = persisted.id.clone();
+                inner.timers.insert(id.clone(), TimerInstance {
+                    id: persisted.id,
+                    name: persisted.name,
+                    duration_ms: persisted.duration_ms,
+                    remaining_ms: restored.remaining_ms,
+                    target_at: restored.target_at,
+                    running: restored.running,
+                    completed: restored.completed,
+                    created_at: persisted.created_at,
+                    mode: persisted.mode,
+                    max_cycles: persisted.max_cycles,
+                    cycle_count: restored.cycle_count,
+                });
+
+                if let Some(target) = restored.target_at {
+                    inner.heap.push(Reverse((target, id)));
+                }
+            }
+        }
+
+        for timer_id in caught_up_ids {
+            let payload = TimerDonePayload { timer_id, finished_at: now_utc.to_rfc3339() };
+            let _ = app.emit("timer:done", payload);
+        }
+
+        state.ensure_driver(app.clone());
+        state
+    }
+
+    pub fn create_timer(
+        &self,
+        id: String,
+        name: String,
+        duration_ms: i64,
+        mode: TimerMode,
+        max_cycles: Option<u32>,
+    ) -> Result<Timer, String> {
+        let mut inner = self.0.inner.lock().map_err(|_| "Failed to lock timer state".to_string())?;
+
         if inner.timers.contains_key(&id) {
             return Err("Timer with this ID already exists".to_string());
         }
@@ -75,15 +302,19 @@ impl TimerState {
             duration_ms,
             remaining_ms: duration_ms,
             target_at: None,
-            runner: None,
-            cancel_tx: None,
             running: false,
             completed: false,
             created_at: created_at.clone(),
+            mode,
+            max_cycles,
+            cycle_count: 0,
         };
 
         inner.timers.insert(id.clone(), timer_instance);
-        
+        drop(inner);
+        self.0.notify.notify_one();
+        self.schedule_save();
+
         Ok(Timer {
             id,
             name,
@@ -92,11 +323,14 @@ impl TimerState {
             running: false,
             completed: false,
             created_at,
+            mode,
+            max_cycles,
+            cycle_count: 0,
         })
     }
 
     pub fn get_all_timers(&self) -> Vec<Timer> {
-        let inner = self.0.lock().unwrap();
+        let inner = self.0.inner.lock().unwrap();
         inner.timers.values().map(|instance| Timer {
             id: instance.id.clone(),
             name: instance.name.clone(),
@@ -105,20 +339,20 @@ impl TimerState {
             running: instance.running,
             completed: instance.completed,
             created_at: instance.created_at.clone(),
+            mode: instance.mode,
+            max_cycles: instance.max_cycles,
+            cycle_count: instance.cycle_count,
         }).collect()
     }
 
     pub fn delete_timer(&self, timer_id: &str) -> Result<(), String> {
-        let mut inner = self.0.lock().unwrap();
-        
-        if let Some(mut timer) = inner.timers.remove(timer_id) {
-            // Cancel the timer if it's running
-            if let Some(tx) = timer.cancel_tx.take() {
-                let _ = tx.send(());
-            }
-            if let Some(handle) = timer.runner.take() {
-                handle.abort();
-            }
+        let mut inner = self.0.inner.lock().unwrap();
+
+        if inner.timers.remove(timer_id).is_some() {
+            // The heap may still hold a deadline for this id; the driver
+            // drops it once it finds no matching timer.
+            drop(inner);
+            self.schedule_save();
             Ok(())
         } else {
             Err("Timer not found".to_string())
@@ -126,20 +360,15 @@ impl TimerState {
     }
 
     pub fn start_timer(&self, app: AppHandle, timer_id: &str) -> Result<(), String> {
-        let mut inner = self.0.lock().unwrap();
-        
+        self.ensure_driver(app.clone());
+
+        let mut inner = self.0.inner.lock().unwrap();
+
         let timer = inner.timers.get_mut(timer_id)
             .ok_or("Timer not found")?;
 
-        // Cancel existing runner if any
-        if let Some(tx) = timer.cancel_tx.take() {
-            let _ = tx.send(());
-        }
-        if let Some(handle) = timer.runner.take() {
-            handle.abort();
-        }
-
-        timer.target_at = Some(Instant::now() + Duration::from_millis(timer.remaining_ms as u64));
+        let target_at = Instant::now() + Duration::from_millis(timer.remaining_ms.max(0) as u64);
+        timer.target_at = Some(target_at);
         timer.running = true;
         timer.completed = false;
 
@@ -150,72 +379,20 @@ impl TimerState {
             duration_ms: timer.duration_ms,
             running: timer.running
         };
-        let _ = app.emit("timer:tick", immediate_payload);
 
-        let (tx, mut rx) = oneshot::channel::<()>();
-        timer.cancel_tx = Some(tx);
-        
-        let state = self.clone();
-        let id = timer_id.to_string();
-
-        timer.runner = Some(tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(1));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        let remaining = {
-                            let mut guard = state.0.lock().unwrap();
-                            if let Some(timer) = guard.timers.get_mut(&id) {
-                                let now = Instant::now();
-                                let remaining = timer.target_at
-                                    .map(|t| (t.saturating_duration_since(now)).as_millis() as i64)
-                                    .unwrap_or(0);
-                                timer.remaining_ms = remaining.max(0);
-
-                                let payload = TimerTickPayload {
-                                    timer_id: id.clone(),
-                                    remaining_ms: timer.remaining_ms,
-                                    duration_ms: timer.duration_ms,
-                                    running: timer.running
-                                };
-                                let _ = app.emit("timer:tick", payload);
-                                remaining
-                            } else {
-                                0 // Timer was deleted
-                            }
-                        };
-
-                        if remaining <= 0 {
-                            let mut guard = state.0.lock().unwrap();
-                            if let Some(timer) = guard.timers.get_mut(&id) {
-                                timer.running = false;
-                                timer.completed = true;
-                                timer.target_at = None;
-                                timer.cancel_tx = None;
-                                
-                                let payload = TimerDonePayload {
-                                    timer_id: id.clone(),
-                                    finished_at: chrono::Utc::now().to_rfc3339()
-                                };
-                                let _ = app.emit("timer:done", payload);
-                            }
-                            break;
-                        }
-                    }
-                    _ = &mut rx => {
-                        // timer cancelled
-                        break;
-                    }
-                }
-            }
-        }));
+        inner.heap.push(Reverse((target_at, timer_id.to_string())));
+        drop(inner);
+
+        let _ = app.emit("timer:tick", immediate_payload);
+        self.0.notify.notify_one();
+        self.schedule_save();
 
         Ok(())
     }
 
     pub fn pause_timer(&self, app: AppHandle, timer_id: &str) -> Result<(), String> {
-        let mut inner = self.0.lock().unwrap();
-        
+        let mut inner = self.0.inner.lock().unwrap();
+
         let timer = inner.timers.get_mut(timer_id)
             .ok_or("Timer not found")?;
 
@@ -223,13 +400,6 @@ impl TimerState {
             return Ok(());
         }
 
-        if let Some(tx) = timer.cancel_tx.take() {
-            let _ = tx.send(());
-        }
-        if let Some(handle) = timer.runner.take() {
-            handle.abort();
-        }
-
         if let Some(target) = timer.target_at {
             let now = Instant::now();
             timer.remaining_ms = (target.saturating_duration_since(now)).as_millis() as i64;
@@ -245,41 +415,37 @@ impl TimerState {
             running: timer.running
         };
         let _ = app.emit("timer:tick", payload);
+        self.0.notify.notify_one();
+        self.schedule_save();
 
         Ok(())
     }
 
     pub fn resume_timer(&self, app: AppHandle, timer_id: &str) -> Result<(), String> {
         {
-            let inner = self.0.lock().unwrap();
+            let inner = self.0.inner.lock().unwrap();
             let timer = inner.timers.get(timer_id)
                 .ok_or("Timer not found")?;
-            
+
             if timer.running {
                 return Ok(());
             }
         }
-        
+
         self.start_timer(app, timer_id)
     }
 
     pub fn reset_timer(&self, app: AppHandle, timer_id: &str) -> Result<(), String> {
-        let mut inner = self.0.lock().unwrap();
-        
+        let mut inner = self.0.inner.lock().unwrap();
+
         let timer = inner.timers.get_mut(timer_id)
             .ok_or("Timer not found")?;
 
-        if let Some(tx) = timer.cancel_tx.take() {
-            let _ = tx.send(());
-        }
-        if let Some(handle) = timer.runner.take() {
-            handle.abort();
-        }
-
         timer.remaining_ms = timer.duration_ms;
         timer.target_at = None;
         timer.running = false;
         timer.completed = false;
+        timer.cycle_count = 0;
 
         // Emit immediate state update to frontend
         let payload = TimerTickPayload {
@@ -289,22 +455,352 @@ impl TimerState {
             running: timer.running
         };
         let _ = app.emit("timer:tick", payload);
+        self.0.notify.notify_one();
+        self.schedule_save();
 
         Ok(())
     }
+
+    /// Spawns the single scheduler task on first use. Safe to call
+    /// repeatedly; only the first call actually starts the driver.
+    fn ensure_driver(&self, app: AppHandle) {
+        {
+            let mut guard = self.0.app.lock().unwrap();
+            if guard.is_none() {
+                *guard = Some(app);
+            }
+        }
+
+        if self.0.driver_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let state = self.clone();
+        tokio::spawn(async move { state.drive().await });
+    }
+
+    /// The long-lived scheduler loop: wakes for whichever comes first of the
+    /// earliest deadline, the coarse tick interval, or a `Notify` signal
+    /// fired by create/start/pause/reset, and acts accordingly.
+    async fn drive(self) {
+        let mut tick = tokio::time::interval(TICK_INTERVAL);
+
+        loop {
+            let earliest = {
+                let inner = self.0.inner.lock().unwrap();
+                inner.heap.peek().map(|Reverse((at, _))| *at)
+            };
+
+            let sleep_until_earliest = async {
+                match earliest {
+                    Some(at) => tokio::time::sleep_until(at.into()).await,
+                    None => std::future::pending::<()>().await,
+                }
+            };
+
+            tokio::select! {
+                _ = sleep_until_earliest => {
+                    self.process_expirations();
+                    tokio::task::yield_now().await;
+                }
+                _ = tick.tick() => {
+                    self.emit_ticks();
+                }
+                _ = self.0.notify.notified() => {
+                    // A timer's deadlines changed; loop around to recompute.
+                }
+            }
+        }
+    }
+
+    /// Pops and handles every heap entry whose deadline has passed, up to
+    /// `MAX_EXPIRATIONS_PER_BATCH`, then returns so the driver can yield.
+    fn process_expirations(&self) {
+        let Some(app) = self.0.app.lock().unwrap().clone() else { return };
+        let now = Instant::now();
+
+        for _ in 0..MAX_EXPIRATIONS_PER_BATCH {
+            let entry = {
+                let mut inner = self.0.inner.lock().unwrap();
+                match inner.heap.peek() {
+                    Some(Reverse((at, _))) if *at <= now => inner.heap.pop(),
+                    _ => None,
+                }
+            };
+
+            let Some(Reverse((at, id))) = entry else { break };
+
+            let mut inner = self.0.inner.lock().unwrap();
+            let Some(timer) = inner.timers.get_mut(&id) else { continue };
+
+            // Stale entry: the timer was paused, reset, or restarted since
+            // this deadline was scheduled.
+            if !timer.running || timer.target_at != Some(at) {
+                continue;
+            }
+
+            timer.cycle_count += 1;
+            timer.remaining_ms = 0;
+            let done_payload = TimerDonePayload {
+                timer_id: id.clone(),
+                finished_at: Utc::now().to_rfc3339(),
+            };
+
+            let exhausted = matches!(timer.max_cycles, Some(max) if timer.cycle_count >= max);
+            if timer.mode == TimerMode::Repeated && !exhausted {
+                let next_target = Instant::now() + Duration::from_millis(timer.duration_ms.max(0) as u64);
+                timer.remaining_ms = timer.duration_ms;
+                timer.target_at = Some(next_target);
+                inner.heap.push(Reverse((next_target, id.clone())));
+            } else {
+                timer.running = false;
+                timer.completed = true;
+                timer.target_at = None;
+            }
+            drop(inner);
+
+            let _ = app.emit("timer:done", done_payload);
+            self.schedule_save();
+        }
+    }
+
+    /// Recomputes `remaining_ms` for every running timer and emits one
+    /// `timer:tick` payload per timer in a single pass.
+    fn emit_ticks(&self) {
+        let Some(app) = self.0.app.lock().unwrap().clone() else { return };
+        let now = Instant::now();
+
+        let payloads: Vec<TimerTickPayload> = {
+            let mut inner = self.0.inner.lock().unwrap();
+            inner.timers.values_mut()
+                .filter(|timer| timer.running)
+                .map(|timer| {
+                    if let Some(target) = timer.target_at {
+                        timer.remaining_ms = target.saturating_duration_since(now).as_millis() as i64;
+                    }
+                    TimerTickPayload {
+                        timer_id: timer.id.clone(),
+                        remaining_ms: timer.remaining_ms,
+                        duration_ms: timer.duration_ms,
+                        running: timer.running,
+                    }
+                })
+                .collect()
+        };
+
+        for payload in payloads {
+            let _ = app.emit("timer:tick", payload);
+        }
+    }
+
+    /// Debounces a persisted snapshot write: schedules a save after
+    /// `SAVE_DEBOUNCE`, but skips it if a newer mutation arrived in the
+    /// meantime (that mutation's own scheduled save will cover this one).
+    fn schedule_save(&self) {
+        let Some(app) = self.0.app.lock().unwrap().clone() else { return };
+        let generation = self.0.save_generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let state = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(SAVE_DEBOUNCE).await;
+            if state.0.save_generation.load(Ordering::SeqCst) == generation {
+                let _ = tokio::task::spawn_blocking(move || state.save_to_disk(&app)).await;
+            }
+        });
+    }
+
+    fn save_to_disk(&self, app: &AppHandle) {
+        let Some(path) = storage_path(app) else { return };
+
+        let persisted: Vec<PersistedTimer> = {
+            let inner = self.0.inner.lock().unwrap();
+            inner.timers.values().map(|timer| PersistedTimer {
+                id: timer.id.clone(),
+                name: timer.name.clone(),
+                duration_ms: timer.duration_ms,
+                remaining_ms: timer.remaining_ms,
+                running: timer.running,
+                completed: timer.completed,
+                created_at: timer.created_at.clone(),
+                mode: timer.mode,
+                max_cycles: timer.max_cycles,
+                cycle_count: timer.cycle_count,
+                deadline: timer.target_at.map(instant_to_rfc3339),
+            }).collect()
+        };
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_vec_pretty(&persisted) {
+            let _ = std::fs::write(&path, json);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn make_persisted(
+        running: bool,
+        mode: TimerMode,
+        max_cycles: Option<u32>,
+        cycle_count: u32,
+        duration_ms: i64,
+        deadline: Option<String>,
+    ) -> PersistedTimer {
+        PersistedTimer {
+            id: "t".to_string(),
+            name: "Test".to_string(),
+            duration_ms,
+            remaining_ms: duration_ms,
+            running,
+            completed: false,
+            created_at: "2024-01-01T00:00:00+00:00".to_string(),
+            mode,
+            max_cycles,
+            cycle_count,
+            deadline,
+        }
+    }
+
+    #[test]
+    fn test_restore_timer_still_pending_keeps_running() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        let deadline = (now_utc + chrono::Duration::milliseconds(4000)).to_rfc3339();
+        let persisted = make_persisted(true, TimerMode::SingleShot, None, 0, 5000, Some(deadline));
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert!(restored.running);
+        assert!(!restored.completed);
+        assert!(!restored.caught_up);
+        assert_eq!(restored.cycle_count, 0);
+        assert!(restored.remaining_ms > 0 && restored.remaining_ms <= 4000);
+        assert!(restored.target_at.is_some());
+    }
+
+    #[test]
+    fn test_restore_timer_single_shot_expired_completes() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        let deadline = (now_utc - chrono::Duration::milliseconds(1500)).to_rfc3339();
+        let persisted = make_persisted(true, TimerMode::SingleShot, None, 0, 5000, Some(deadline));
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert!(!restored.running);
+        assert!(restored.completed);
+        assert!(restored.caught_up);
+        assert_eq!(restored.remaining_ms, 0);
+        assert_eq!(restored.cycle_count, 1);
+        assert!(restored.target_at.is_none());
+    }
+
+    #[test]
+    fn test_restore_timer_repeated_catches_up_one_period() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        // 1.5 periods of a 1000ms timer elapsed
+        let deadline = (now_utc - chrono::Duration::milliseconds(1500)).to_rfc3339();
+        let persisted = make_persisted(true, TimerMode::Repeated, None, 0, 1000, Some(deadline));
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert!(restored.running);
+        assert!(!restored.completed);
+        assert!(restored.caught_up);
+        assert_eq!(restored.cycle_count, 2);
+        // 1500ms elapsed against a 1000ms period: 500ms left into the current cycle
+        assert_eq!(restored.remaining_ms, 500);
+        assert!(restored.target_at.is_some());
+    }
+
+    #[test]
+    fn test_restore_timer_repeated_catches_up_several_periods() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        // Closed for 10.25 periods of a 1000ms timer
+        let deadline = (now_utc - chrono::Duration::milliseconds(10_250)).to_rfc3339();
+        let persisted = make_persisted(true, TimerMode::Repeated, None, 0, 1000, Some(deadline));
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert!(restored.running);
+        assert_eq!(restored.cycle_count, 11);
+        assert_eq!(restored.remaining_ms, 750);
+    }
+
+    #[test]
+    fn test_restore_timer_saturates_instead_of_overflowing_cycle_count() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        // A 1ms period left closed for 60 days elapses more than u32::MAX
+        // periods; this must saturate rather than panic or wrap.
+        let deadline = (now_utc - chrono::Duration::days(60)).to_rfc3339();
+        let persisted = make_persisted(true, TimerMode::Repeated, None, 0, 1, Some(deadline));
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert_eq!(restored.cycle_count, u32::MAX);
+        assert!(restored.running);
+        assert!(!restored.completed);
+    }
+
+    #[test]
+    fn test_restore_timer_repeated_exhausts_max_cycles_on_restore() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        // Closed for 5 periods, but only 4 cycles are allowed and 2 already ran
+        let deadline = (now_utc - chrono::Duration::milliseconds(5000)).to_rfc3339();
+        let persisted = make_persisted(true, TimerMode::Repeated, Some(4), 2, 1000, Some(deadline));
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert!(!restored.running);
+        assert!(restored.completed);
+        assert!(restored.caught_up);
+        assert_eq!(restored.remaining_ms, 0);
+        assert!(restored.cycle_count >= 4);
+    }
+
+    #[test]
+    fn test_restore_timer_not_running_is_left_alone() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        let persisted = make_persisted(false, TimerMode::SingleShot, None, 0, 5000, None);
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert!(!restored.running);
+        assert!(!restored.caught_up);
+        assert!(restored.target_at.is_none());
+    }
+
+    #[test]
+    fn test_restore_timer_missing_deadline_stops_running() {
+        let now_utc = Utc::now();
+        let now_instant = Instant::now();
+        let persisted = make_persisted(true, TimerMode::SingleShot, None, 0, 5000, None);
+
+        let restored = restore_timer(&persisted, now_utc, now_instant);
+
+        assert!(!restored.running);
+        assert!(!restored.caught_up);
+        assert!(restored.target_at.is_none());
+    }
+
     #[test]
     fn test_create_timer() {
         let state = TimerState::new();
         let timer = state.create_timer(
             "test1".to_string(),
             "Test Timer".to_string(),
-            5000
+            5000,
+            TimerMode::SingleShot,
+            None,
         ).unwrap();
 
         assert_eq!(timer.id, "test1");
@@ -318,12 +814,12 @@ mod tests {
     #[test]
     fn test_create_duplicate_timer_fails() {
         let state = TimerState::new();
-        
+
         // Create first timer
-        state.create_timer("test1".to_string(), "Test Timer".to_string(), 5000).unwrap();
-        
+        state.create_timer("test1".to_string(), "Test Timer".to_string(), 5000, TimerMode::SingleShot, None).unwrap();
+
         // Try to create duplicate - should fail
-        let result = state.create_timer("test1".to_string(), "Duplicate Timer".to_string(), 3000);
+        let result = state.create_timer("test1".to_string(), "Duplicate Timer".to_string(), 3000, TimerMode::SingleShot, None);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "Timer with this ID already exists");
     }
@@ -331,18 +827,18 @@ mod tests {
     #[test]
     fn test_get_all_timers() {
         let state = TimerState::new();
-        
+
         // Initially empty
         assert_eq!(state.get_all_timers().len(), 0);
-        
+
         // Add a timer
-        state.create_timer("test1".to_string(), "Test Timer 1".to_string(), 5000).unwrap();
+        state.create_timer("test1".to_string(), "Test Timer 1".to_string(), 5000, TimerMode::SingleShot, None).unwrap();
         let timers = state.get_all_timers();
         assert_eq!(timers.len(), 1);
         assert_eq!(timers[0].id, "test1");
-        
+
         // Add another timer
-        state.create_timer("test2".to_string(), "Test Timer 2".to_string(), 3000).unwrap();
+        state.create_timer("test2".to_string(), "Test Timer 2".to_string(), 3000, TimerMode::SingleShot, None).unwrap();
         let timers = state.get_all_timers();
         assert_eq!(timers.len(), 2);
     }
@@ -350,16 +846,16 @@ mod tests {
     #[test]
     fn test_delete_timer() {
         let state = TimerState::new();
-        
+
         // Create timer
-        state.create_timer("test1".to_string(), "Test Timer".to_string(), 5000).unwrap();
+        state.create_timer("test1".to_string(), "Test Timer".to_string(), 5000, TimerMode::SingleShot, None).unwrap();
         assert_eq!(state.get_all_timers().len(), 1);
-        
+
         // Delete timer
         let result = state.delete_timer("test1");
         assert!(result.is_ok());
         assert_eq!(state.get_all_timers().len(), 0);
-        
+
         // Try to delete non-existent timer
         let result = state.delete_timer("nonexistent");
         assert!(result.is_err());
@@ -372,7 +868,9 @@ mod tests {
         let timer = state.create_timer(
             "test1".to_string(),
             "Test Timer".to_string(),
-            10000
+            10000,
+            TimerMode::SingleShot,
+            None,
         ).unwrap();
 
         // Timer should start in a stopped state
@@ -385,11 +883,11 @@ mod tests {
     #[test]
     fn test_timer_not_found_operations() {
         let state = TimerState::new();
-        
+
         // We can't easily test the start/pause/resume/reset functions without a proper AppHandle
         // but we can at least test that error handling works for timer lookup
         {
-            let inner = state.0.lock().unwrap();
+            let inner = state.0.inner.lock().unwrap();
             assert!(inner.timers.get("nonexistent").is_none());
         }
     }
@@ -400,7 +898,9 @@ mod tests {
         let timer = state.create_timer(
             "zero_timer".to_string(),
             "Zero Duration Timer".to_string(),
-            0
+            0,
+            TimerMode::SingleShot,
+            None,
         ).unwrap();
 
         assert_eq!(timer.duration_ms, 0);
@@ -415,7 +915,9 @@ mod tests {
         let timer = state.create_timer(
             "negative_timer".to_string(),
             "Negative Duration Timer".to_string(),
-            -1000
+            -1000,
+            TimerMode::SingleShot,
+            None,
         ).unwrap();
 
         // System allows negative durations (might be intentional behavior)
@@ -427,15 +929,17 @@ mod tests {
     fn test_timer_created_at_timestamp() {
         let state = TimerState::new();
         let before = chrono::Utc::now();
-        
+
         let timer = state.create_timer(
             "timestamp_test".to_string(),
             "Timestamp Test".to_string(),
-            5000
+            5000,
+            TimerMode::SingleShot,
+            None,
         ).unwrap();
-        
+
         let after = chrono::Utc::now();
-        
+
         // Parse the timestamp and verify it's within reasonable bounds
         let created_at = chrono::DateTime::parse_from_rfc3339(&timer.created_at).unwrap();
         let created_at_utc = created_at.with_timezone(&chrono::Utc);
@@ -446,25 +950,25 @@ mod tests {
     #[test]
     fn test_multiple_timer_management() {
         let state = TimerState::new();
-        
+
         // Create multiple timers with different properties
-        let _timer1 = state.create_timer("short".to_string(), "Short Timer".to_string(), 1000).unwrap();
-        let _timer2 = state.create_timer("long".to_string(), "Long Timer".to_string(), 60000).unwrap();
-        let _timer3 = state.create_timer("medium".to_string(), "Medium Timer".to_string(), 10000).unwrap();
-        
+        let _timer1 = state.create_timer("short".to_string(), "Short Timer".to_string(), 1000, TimerMode::SingleShot, None).unwrap();
+        let _timer2 = state.create_timer("long".to_string(), "Long Timer".to_string(), 60000, TimerMode::SingleShot, None).unwrap();
+        let _timer3 = state.create_timer("medium".to_string(), "Medium Timer".to_string(), 10000, TimerMode::SingleShot, None).unwrap();
+
         let all_timers = state.get_all_timers();
         assert_eq!(all_timers.len(), 3);
-        
+
         // Verify we can find each timer by ID
         let timer_ids: std::collections::HashSet<String> = all_timers.iter().map(|t| t.id.clone()).collect();
         assert!(timer_ids.contains("short"));
         assert!(timer_ids.contains("long"));
         assert!(timer_ids.contains("medium"));
-        
+
         // Delete one timer and verify count
         state.delete_timer("medium").unwrap();
         assert_eq!(state.get_all_timers().len(), 2);
-        
+
         // Verify the right timer was deleted
         let remaining_ids: std::collections::HashSet<String> = state.get_all_timers().iter().map(|t| t.id.clone()).collect();
         assert!(remaining_ids.contains("short"));
@@ -475,36 +979,79 @@ mod tests {
     #[test]
     fn test_timer_name_handling() {
         let state = TimerState::new();
-        
+
         // Test empty name
-        let timer1 = state.create_timer("empty_name".to_string(), "".to_string(), 5000).unwrap();
+        let timer1 = state.create_timer("empty_name".to_string(), "".to_string(), 5000, TimerMode::SingleShot, None).unwrap();
         assert_eq!(timer1.name, "");
-        
+
         // Test long name
         let long_name = "A".repeat(1000);
-        let timer2 = state.create_timer("long_name".to_string(), long_name.clone(), 5000).unwrap();
+        let timer2 = state.create_timer("long_name".to_string(), long_name.clone(), 5000, TimerMode::SingleShot, None).unwrap();
         assert_eq!(timer2.name, long_name);
-        
+
         // Test name with special characters
-        let special_name = "Timer with √©mojis üïê and symbols!@#$%^&*()";
-        let timer3 = state.create_timer("special_name".to_string(), special_name.to_string(), 5000).unwrap();
+        let special_name = "Timer with √©mojis üïê and symbols!@#$%^&*()";
+        let timer3 = state.create_timer("special_name".to_string(), special_name.to_string(), 5000, TimerMode::SingleShot, None).unwrap();
         assert_eq!(timer3.name, special_name);
     }
 
     #[test]
     fn test_timer_id_edge_cases() {
         let state = TimerState::new();
-        
+
         // Test empty ID
-        let timer1 = state.create_timer("".to_string(), "Empty ID Timer".to_string(), 5000).unwrap();
+        let timer1 = state.create_timer("".to_string(), "Empty ID Timer".to_string(), 5000, TimerMode::SingleShot, None).unwrap();
         assert_eq!(timer1.id, "");
-        
+
         // Test very long ID
         let long_id = "x".repeat(1000);
-        let timer2 = state.create_timer(long_id.clone(), "Long ID Timer".to_string(), 5000).unwrap();
+        let timer2 = state.create_timer(long_id.clone(), "Long ID Timer".to_string(), 5000, TimerMode::SingleShot, None).unwrap();
         assert_eq!(timer2.id, long_id);
-        
+
         // Verify both timers exist
         assert_eq!(state.get_all_timers().len(), 2);
     }
+
+    #[test]
+    fn test_create_repeated_timer_defaults() {
+        let state = TimerState::new();
+        let timer = state.create_timer(
+            "repeat1".to_string(),
+            "Standup Reminder".to_string(),
+            5000,
+            TimerMode::Repeated,
+            Some(4),
+        ).unwrap();
+
+        assert_eq!(timer.mode, TimerMode::Repeated);
+        assert_eq!(timer.max_cycles, Some(4));
+        assert_eq!(timer.cycle_count, 0);
+    }
+
+    #[test]
+    fn test_create_single_shot_timer_has_no_cycle_limit_by_default() {
+        let state = TimerState::new();
+        let timer = state.create_timer(
+            "oneshot1".to_string(),
+            "One Shot".to_string(),
+            5000,
+            TimerMode::SingleShot,
+            None,
+        ).unwrap();
+
+        assert_eq!(timer.mode, TimerMode::SingleShot);
+        assert_eq!(timer.max_cycles, None);
+        assert_eq!(timer.cycle_count, 0);
+    }
+
+    #[test]
+    fn test_heap_keeps_earliest_deadline_first() {
+        let mut heap: BinaryHeap<Reverse<(Instant, String)>> = BinaryHeap::new();
+        let now = Instant::now();
+        heap.push(Reverse((now + Duration::from_secs(5), "later".to_string())));
+        heap.push(Reverse((now + Duration::from_secs(1), "sooner".to_string())));
+
+        let Reverse((_, id)) = heap.pop().unwrap();
+        assert_eq!(id, "sooner");
+    }
 }